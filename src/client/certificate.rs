@@ -0,0 +1,409 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    sync::{mpsc::Sender, Arc},
+    time::SystemTime,
+};
+
+use bevy::prelude::Event;
+use rustls::{
+    client::{ServerCertVerified, ServerCertVerifier},
+    Certificate, CertificateError, ClientConfig, Error as RustlsError, RootCertStore, ServerName,
+};
+
+use crate::{
+    client::connection::{ConnectionId, InternalAsyncMessage},
+    shared::{CertificateFingerprint, QuinnetError},
+};
+
+/// Default file used to persist known server certificate fingerprints between runs, when using
+/// [`CertificateVerificationMode::TrustOnFirstUse`].
+pub const DEFAULT_KNOWN_HOSTS_FILE: &str = "quinnet/known_hosts";
+
+/// Outcome of comparing a server certificate against the client's trust store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CertVerificationStatus {
+    /// The certificate matches a fingerprint the client already trusts.
+    Trusted,
+    /// The client has no prior knowledge of this server, the certificate was stored.
+    UnknownCertificate,
+    /// The certificate does not match the fingerprint previously known for this server.
+    UntrustedCertificate,
+}
+
+/// Info about a server certificate, surfaced to the app during certificate verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertificateInfo {
+    pub fingerprint: CertificateFingerprint,
+    pub known_fingerprint: Option<CertificateFingerprint>,
+    pub server_name: ServerName,
+}
+
+/// Concrete certificate fault reported alongside a [`CertVerificationStatus`] (for an unknown
+/// certificate as well as an untrusted one), beyond a plain fingerprint mismatch. Mirrors the
+/// faults rustls can report while validating a certificate chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CertVerificationError {
+    /// The certificate's `notAfter` date is in the past.
+    Expired,
+    /// The certificate's `notBefore` date is in the future.
+    NotYetValid,
+    /// The certificate's signature could not be verified against its issuer.
+    BadSignature,
+    /// The certificate does not cover the server name we connected to.
+    NameMismatch,
+    /// The certificate's issuer is not in the trusted root store.
+    UnknownIssuer,
+    /// The certificate has been revoked by its issuer.
+    Revoked,
+    /// Any other certificate fault reported by rustls.
+    Other(String),
+}
+
+impl From<&RustlsError> for CertVerificationError {
+    fn from(err: &RustlsError) -> Self {
+        match err {
+            RustlsError::InvalidCertificate(CertificateError::Expired) => {
+                CertVerificationError::Expired
+            }
+            RustlsError::InvalidCertificate(CertificateError::NotValidYet) => {
+                CertVerificationError::NotYetValid
+            }
+            RustlsError::InvalidCertificate(CertificateError::BadSignature) => {
+                CertVerificationError::BadSignature
+            }
+            RustlsError::InvalidCertificate(CertificateError::NotValidForName) => {
+                CertVerificationError::NameMismatch
+            }
+            RustlsError::InvalidCertificate(CertificateError::UnknownIssuer) => {
+                CertVerificationError::UnknownIssuer
+            }
+            RustlsError::InvalidCertificate(CertificateError::Revoked) => {
+                CertVerificationError::Revoked
+            }
+            other => CertVerificationError::Other(other.to_string()),
+        }
+    }
+}
+
+/// Sent the first time the client sees (and pins) a server's certificate. If that certificate
+/// has a concrete fault, a [`CertificateInteractionEvent`] is sent instead.
+#[derive(Event, Debug, Clone)]
+pub struct CertificateTrustUpdateEvent {
+    pub cert_info: CertificateInfo,
+}
+
+/// Sent when the client cannot automatically decide whether to trust a server certificate and
+/// needs the app to choose a [`CertificateAction`], applied via
+/// [`Client::apply_certificate_action`](crate::client::Client::apply_certificate_action).
+#[derive(Event, Debug, Clone)]
+pub struct CertificateInteractionEvent {
+    pub connection_id: ConnectionId,
+    pub status: CertVerificationStatus,
+    pub info: CertificateInfo,
+    /// Set when the verification failure is a concrete certificate fault rather than a plain
+    /// TOFU fingerprint mismatch.
+    pub error: Option<CertVerificationError>,
+}
+
+/// Sent when a connection was aborted as a result of certificate verification.
+#[derive(Event, Debug, Clone)]
+pub struct CertificateVerificationConnectionAbortEvent {
+    pub connection_id: ConnectionId,
+    pub status: CertVerificationStatus,
+    pub info: CertificateInfo,
+    pub error: Option<CertVerificationError>,
+}
+
+/// Action requested by the app in response to a [`CertificateInteractionEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CertificateAction {
+    /// Abort the in-progress connection.
+    AbortConnection,
+    /// Trust the certificate for this connection. Applying this action does not write its
+    /// fingerprint to the known hosts file.
+    TrustAndStore,
+    /// Trust the certificate for this connection only, without persisting it.
+    TrustOnce,
+}
+
+/// Config for [`CertificateVerificationMode::TrustOnFirstUse`].
+#[derive(Debug, Clone)]
+pub struct TrustOnFirstUseConfig {
+    /// File used to persist known server certificate fingerprints.
+    pub known_hosts_file: String,
+}
+
+impl Default for TrustOnFirstUseConfig {
+    fn default() -> Self {
+        Self {
+            known_hosts_file: DEFAULT_KNOWN_HOSTS_FILE.to_string(),
+        }
+    }
+}
+
+/// Config for [`CertificateVerificationMode::TrustStandardRoots`].
+#[derive(Debug, Clone, Default)]
+pub struct TrustStandardRootsConfig {
+    /// Additional PEM-encoded root certificates to trust, on top of the platform's native root
+    /// store. Useful for private CAs that are not part of the OS trust store.
+    pub additional_root_certs_pem_files: Vec<String>,
+}
+
+/// How the client should verify the certificate presented by a server during the TLS handshake.
+#[derive(Debug, Clone)]
+pub enum CertificateVerificationMode {
+    /// Accept any certificate, does not record or check anything. Only meant for quick local
+    /// testing, never use this for a real deployment.
+    SkipVerification,
+    /// Pin the server certificate on first connection and compare it against the pinned
+    /// fingerprint on subsequent connections, persisting known fingerprints to disk. Suited for
+    /// deployments using self-signed certificates.
+    TrustOnFirstUse(TrustOnFirstUseConfig),
+    /// Validate the server certificate chain against the platform's native root store (and any
+    /// extra roots supplied by the app), plus the usual hostname checks. Use this when the
+    /// server presents a certificate signed by a well-known CA (e.g. Let's Encrypt).
+    TrustStandardRoots(TrustStandardRootsConfig),
+}
+
+/// Builds the rustls [`ClientConfig`] matching `mode`, wired to report certificate trust
+/// decisions for `connection_id` on `sender`, and advertising `alpn_protocols`.
+pub(crate) fn build_client_config(
+    mode: &CertificateVerificationMode,
+    server_name: &str,
+    connection_id: ConnectionId,
+    sender: Sender<InternalAsyncMessage>,
+    alpn_protocols: &[Vec<u8>],
+) -> Result<ClientConfig, QuinnetError> {
+    let mut config = match mode {
+        CertificateVerificationMode::SkipVerification => ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(std::sync::Arc::new(SkipServerVerification))
+            .with_no_client_auth(),
+        CertificateVerificationMode::TrustOnFirstUse(tofu_config) => ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(std::sync::Arc::new(TofuServerCertVerifier {
+                known_hosts_file: tofu_config.known_hosts_file.clone(),
+                server_name: server_name.to_string(),
+                connection_id,
+                sender,
+            }))
+            .with_no_client_auth(),
+        CertificateVerificationMode::TrustStandardRoots(roots_config) => {
+            return build_standard_roots_client_config(roots_config, alpn_protocols)
+        }
+    };
+    config.alpn_protocols = alpn_protocols.to_vec();
+    Ok(config)
+}
+
+/// Builds a rustls [`ClientConfig`] that validates server certificates against the platform's
+/// native root store, plus any additional PEM-encoded roots supplied by the app.
+pub(crate) fn build_standard_roots_client_config(
+    config: &TrustStandardRootsConfig,
+    alpn_protocols: &[Vec<u8>],
+) -> Result<ClientConfig, QuinnetError> {
+    let mut root_store = RootCertStore::empty();
+
+    for cert in rustls_native_certs::load_native_certs().map_err(QuinnetError::IoError)? {
+        // A malformed entry in the OS store should not prevent the rest from loading.
+        let _ = root_store.add(&Certificate(cert.0));
+    }
+
+    for pem_file in &config.additional_root_certs_pem_files {
+        let pem = fs::read(Path::new(pem_file)).map_err(QuinnetError::IoError)?;
+        for cert in rustls_pemfile::certs(&mut pem.as_slice()).map_err(QuinnetError::IoError)? {
+            let _ = root_store.add(&Certificate(cert));
+        }
+    }
+
+    let mut config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(root_store)
+        .with_no_client_auth();
+    config.alpn_protocols = alpn_protocols.to_vec();
+
+    Ok(config)
+}
+
+pub(crate) fn server_name_from_str(name: &str) -> Result<ServerName, QuinnetError> {
+    ServerName::try_from(name).map_err(QuinnetError::InvalidDnsName)
+}
+
+fn load_known_hosts(path: &str) -> HashMap<String, CertificateFingerprint> {
+    let mut known_hosts = HashMap::new();
+    let Ok(contents) = fs::read_to_string(path) else {
+        return known_hosts;
+    };
+    for line in contents.lines() {
+        if let Some((server_name, fingerprint_b64)) = line.split_once(' ') {
+            if let Ok(bytes) = base64::decode(fingerprint_b64) {
+                if let Ok(buf) = <[u8; 32]>::try_from(bytes) {
+                    known_hosts.insert(server_name.to_string(), CertificateFingerprint::new(buf));
+                }
+            }
+        }
+    }
+    known_hosts
+}
+
+fn save_known_hosts(
+    path: &str,
+    known_hosts: &HashMap<String, CertificateFingerprint>,
+) -> Result<(), QuinnetError> {
+    if let Some(parent) = Path::new(path).parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent).map_err(QuinnetError::CreateHostsFile)?;
+        }
+    }
+    let contents = known_hosts
+        .iter()
+        .map(|(server_name, fingerprint)| format!("{} {}", server_name, fingerprint.to_base64()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    fs::write(path, contents).map_err(QuinnetError::CreateHostsFile)
+}
+
+/// Accepts any certificate without inspecting it. Backs
+/// [`CertificateVerificationMode::SkipVerification`].
+struct SkipServerVerification;
+
+impl ServerCertVerifier for SkipServerVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, RustlsError> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+/// Implements trust-on-first-use pinning: the first certificate seen for a server name is
+/// stored in `known_hosts_file` and trusted from then on; a later mismatch is reported via
+/// [`InternalAsyncMessage::CertInteraction`] instead of failing the handshake outright, so the
+/// app can inspect it and decide whether to keep or abort the connection.
+struct TofuServerCertVerifier {
+    known_hosts_file: String,
+    server_name: String,
+    connection_id: ConnectionId,
+    sender: Sender<InternalAsyncMessage>,
+}
+
+impl ServerCertVerifier for TofuServerCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, RustlsError> {
+        let fingerprint = CertificateFingerprint::from(end_entity);
+        let mut known_hosts = load_known_hosts(&self.known_hosts_file);
+        let known_fingerprint = known_hosts.get(&self.server_name).cloned();
+
+        let info = CertificateInfo {
+            fingerprint: fingerprint.clone(),
+            known_fingerprint: known_fingerprint.clone(),
+            server_name: server_name.clone(),
+        };
+
+        // TOFU pinning does not establish a chain of trust, but it should still catch a
+        // presented certificate that is invalid on its own terms (expired, not yet valid, or for
+        // the wrong name): validate it against itself, as if it were its own root. This only
+        // succeeds in building a path for a genuinely self-signed certificate, so for a
+        // CA-issued one `validate_self_signed` always fails with `UnknownIssuer`/`BadSignature` -
+        // that's expected and not a real fault, so only the faults it can reliably attribute to
+        // the certificate's own content (not to it lacking a matching root) are kept.
+        let cert_fault = validate_self_signed(end_entity, server_name, now)
+            .err()
+            .map(|err| CertVerificationError::from(&err))
+            .filter(|fault| {
+                matches!(
+                    fault,
+                    CertVerificationError::Expired
+                        | CertVerificationError::NotYetValid
+                        | CertVerificationError::NameMismatch
+                        | CertVerificationError::Revoked
+                )
+            });
+
+        match known_fingerprint {
+            None => {
+                known_hosts.insert(self.server_name.clone(), fingerprint);
+                let _ = save_known_hosts(&self.known_hosts_file, &known_hosts);
+                match cert_fault {
+                    // Still pin it (TOFU trusts whatever it first sees), but let the app decide
+                    // whether to proceed given the fault, rather than reporting it as a plain
+                    // trust update.
+                    Some(fault) => {
+                        let _ = self.sender.send(InternalAsyncMessage::CertInteraction(
+                            self.connection_id,
+                            CertVerificationStatus::UnknownCertificate,
+                            info,
+                            Some(fault),
+                        ));
+                    }
+                    None => {
+                        let _ = self.sender.send(InternalAsyncMessage::CertTrustUpdate(
+                            self.connection_id,
+                            info,
+                        ));
+                    }
+                }
+                Ok(ServerCertVerified::assertion())
+            }
+            Some(known) if known == fingerprint => {
+                if let Some(fault) = cert_fault {
+                    // The pinned certificate matches, but is no longer valid on its own terms
+                    // (e.g. it expired since it was pinned); let the app decide whether to keep
+                    // trusting it rather than silently accepting a stale certificate.
+                    let _ = self.sender.send(InternalAsyncMessage::CertInteraction(
+                        self.connection_id,
+                        CertVerificationStatus::UntrustedCertificate,
+                        info,
+                        Some(fault),
+                    ));
+                }
+                Ok(ServerCertVerified::assertion())
+            }
+            Some(_) => {
+                let _ = self.sender.send(InternalAsyncMessage::CertInteraction(
+                    self.connection_id,
+                    CertVerificationStatus::UntrustedCertificate,
+                    info,
+                    cert_fault,
+                ));
+                // Let the handshake complete: the app decides whether to abort the connection
+                // once it has reacted to the `CertificateInteractionEvent`, rather than failing
+                // here with an opaque rustls error.
+                Ok(ServerCertVerified::assertion())
+            }
+        }
+    }
+}
+
+/// Validates `end_entity` as though it were its own trust root, surfacing the concrete rustls
+/// fault (expiry, name mismatch, bad signature...) if any, without requiring a real certificate
+/// chain. Used by [`TofuServerCertVerifier`] to add validity-period/name checks on top of plain
+/// fingerprint pinning.
+fn validate_self_signed(
+    end_entity: &Certificate,
+    server_name: &ServerName,
+    now: SystemTime,
+) -> Result<(), RustlsError> {
+    let mut roots = RootCertStore::empty();
+    roots
+        .add(end_entity)
+        .map_err(|err| RustlsError::InvalidCertificate(CertificateError::Other(Arc::new(err))))?;
+    let verifier = rustls::client::WebPkiVerifier::new(roots, None);
+    verifier
+        .verify_server_cert(end_entity, &[], server_name, &mut std::iter::empty(), &[], now)
+        .map(|_| ())
+}
@@ -0,0 +1,199 @@
+use std::{net::SocketAddr, time::Duration};
+
+use bevy::prelude::{Deref, DerefMut, Event};
+use serde::{Deserialize, Serialize};
+use tokio::task::AbortHandle;
+
+use crate::{
+    client::certificate::{CertVerificationError, CertVerificationStatus, CertificateInfo},
+    shared::{InternalConnectionRef, QuinnetError},
+};
+
+/// Default duration [`Client::open_connection`](crate::client::Client::open_connection) will
+/// wait for the QUIC handshake to complete before giving up and emitting
+/// [`QuinnetError::ConnectTimeout`](crate::shared::QuinnetError::ConnectTimeout).
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Deref, DerefMut, Serialize, Deserialize,
+)]
+pub struct ConnectionId(pub u64);
+
+impl std::fmt::Display for ConnectionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Describes the server a connection attempt should target, and how that attempt behaves.
+#[derive(Debug, Clone)]
+pub struct ConnectionConfiguration {
+    pub server_addr: SocketAddr,
+    pub server_hostname: String,
+    pub local_bind_addr: SocketAddr,
+    /// How long to wait for the handshake to complete before aborting the attempt and emitting
+    /// a connection-failed event carrying [`QuinnetError::ConnectTimeout`](crate::shared::QuinnetError::ConnectTimeout).
+    pub connect_timeout: Duration,
+    /// ALPN protocol identifiers this connection advertises to the server. Leave empty to not
+    /// advertise any. If the server requires one of its own and none of these match, the
+    /// handshake fails with [`QuinnetError::AlpnNegotiationFailed`](crate::shared::QuinnetError::AlpnNegotiationFailed).
+    pub alpn_protocols: Vec<Vec<u8>>,
+}
+
+impl ConnectionConfiguration {
+    pub fn from_ips(
+        server_ip: std::net::IpAddr,
+        server_port: u16,
+        local_bind_addr: std::net::IpAddr,
+        local_bind_port: u16,
+    ) -> Self {
+        Self {
+            server_addr: SocketAddr::new(server_ip, server_port),
+            server_hostname: server_ip.to_string(),
+            local_bind_addr: SocketAddr::new(local_bind_addr, local_bind_port),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            alpn_protocols: Vec::new(),
+        }
+    }
+
+    pub fn from_strings(
+        server_host: &str,
+        server_port: u16,
+        local_bind_host: &str,
+        local_bind_port: u16,
+    ) -> Result<Self, std::net::AddrParseError> {
+        Ok(Self {
+            server_addr: format!("{}:{}", server_host, server_port).parse()?,
+            server_hostname: server_host.to_string(),
+            local_bind_addr: format!("{}:{}", local_bind_host, local_bind_port).parse()?,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            alpn_protocols: Vec::new(),
+        })
+    }
+
+    /// Overrides the default connect timeout for this connection attempt.
+    pub fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    /// Sets the ALPN protocol identifiers this connection advertises to the server.
+    pub fn with_alpn_protocols(mut self, alpn_protocols: Vec<Vec<u8>>) -> Self {
+        self.alpn_protocols = alpn_protocols;
+        self
+    }
+}
+
+/// Current lifecycle state of a client-side connection.
+pub(crate) enum ConnectionState {
+    /// The QUIC handshake is still in flight.
+    Connecting,
+    /// The handshake completed and the connection is usable.
+    Connected(InternalConnectionRef),
+    /// The connection was closed, aborted or timed out.
+    Disconnected,
+}
+
+/// A pending certificate interaction the app has not yet responded to with a
+/// [`CertificateAction`](crate::client::certificate::CertificateAction).
+pub(crate) struct PendingCertificateInteraction {
+    pub(crate) status: CertVerificationStatus,
+    pub(crate) info: CertificateInfo,
+    pub(crate) error: Option<CertVerificationError>,
+}
+
+/// Client-side bookkeeping for a single connection (attempted or established).
+pub(crate) struct ClientSideConnection {
+    pub(crate) state: ConnectionState,
+    pub(crate) pending_cert_interaction: Option<PendingCertificateInteraction>,
+    /// Handle to the task racing the handshake against `connect_timeout`, so the attempt can be
+    /// cancelled with [`Client::cancel_connection`](crate::client::Client::cancel_connection)
+    /// before it completes. `None` once the connection has settled (connected or failed).
+    pub(crate) abort_handle: Option<AbortHandle>,
+}
+
+impl ClientSideConnection {
+    pub(crate) fn is_connected(&self) -> bool {
+        matches!(self.state, ConnectionState::Connected(_))
+    }
+}
+
+/// Why a connection attempt did not result in an established connection. Surfaced to the app via
+/// [`ConnectionFailedEvent`] instead of requiring it to match on [`QuinnetError`] directly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionFailureReason {
+    /// The handshake did not complete within
+    /// [`ConnectionConfiguration::connect_timeout`].
+    ConnectTimeout,
+    /// The server rejected every ALPN protocol this connection advertised.
+    AlpnNegotiationFailed,
+    /// Any other failure, carrying its `Display` message.
+    Other(String),
+}
+
+impl From<QuinnetError> for ConnectionFailureReason {
+    fn from(err: QuinnetError) -> Self {
+        match err {
+            QuinnetError::ConnectTimeout => ConnectionFailureReason::ConnectTimeout,
+            QuinnetError::AlpnNegotiationFailed => ConnectionFailureReason::AlpnNegotiationFailed,
+            other => ConnectionFailureReason::Other(other.to_string()),
+        }
+    }
+}
+
+/// TLS alert number for `no_application_protocol` (RFC 7301 §3.2). quinn/rustls carry a fatal TLS
+/// alert as a QUIC `CRYPTO_ERROR` whose code is `0x0100 + alert` (RFC 9001 §4.8), which
+/// [`quinn_proto::TransportErrorCode::crypto`] constructs the same way on the sending side.
+const TLS_ALERT_NO_APPLICATION_PROTOCOL: u8 = 120;
+
+/// A server rejecting every ALPN protocol this connection advertised closes the connection with
+/// that `CRYPTO_ERROR` code, which reaches the client as a
+/// [`quinn_proto::ConnectionError::ConnectionClosed`]; the same code raised by the client's own
+/// TLS stack surfaces as a [`quinn_proto::ConnectionError::TransportError`]. Matches on the
+/// structured code rather than the error's `Display` message, which is not guaranteed to
+/// mention "alpn" or "application" (rustls's own message for this alert does not).
+pub(crate) fn classify_connect_error(err: quinn_proto::ConnectionError) -> QuinnetError {
+    let no_application_protocol =
+        quinn_proto::TransportErrorCode::crypto(TLS_ALERT_NO_APPLICATION_PROTOCOL);
+    match &err {
+        quinn_proto::ConnectionError::ConnectionClosed(close)
+            if close.error_code == no_application_protocol =>
+        {
+            QuinnetError::AlpnNegotiationFailed
+        }
+        quinn_proto::ConnectionError::TransportError(transport_error)
+            if transport_error.code == no_application_protocol =>
+        {
+            QuinnetError::AlpnNegotiationFailed
+        }
+        _ => QuinnetError::Connect(err),
+    }
+}
+
+/// Sent when a connection attempt ends without establishing a connection, whether it failed
+/// outright, was refused during the handshake or timed out.
+#[derive(Event, Debug, Clone)]
+pub struct ConnectionFailedEvent {
+    pub connection_id: ConnectionId,
+    pub reason: ConnectionFailureReason,
+}
+
+/// Messages sent from the async networking tasks back to the sync [`Client`](crate::client::Client),
+/// drained once per frame by [`crate::client::update_sync_client`].
+pub(crate) enum InternalAsyncMessage {
+    Connected(ConnectionId, InternalConnectionRef),
+    ConnectionFailed(ConnectionId, QuinnetError),
+    CertTrustUpdate(ConnectionId, CertificateInfo),
+    CertInteraction(
+        ConnectionId,
+        CertVerificationStatus,
+        CertificateInfo,
+        Option<CertVerificationError>,
+    ),
+    CertConnectionAbort(
+        ConnectionId,
+        CertVerificationStatus,
+        CertificateInfo,
+        Option<CertVerificationError>,
+    ),
+}
@@ -0,0 +1,291 @@
+use std::{
+    collections::HashMap,
+    sync::{mpsc, Arc},
+};
+
+use bevy::prelude::{App, EventWriter, Plugin, PreUpdate, ResMut, Resource};
+use quinn::Endpoint;
+
+use crate::{
+    client::{
+        certificate::{
+            CertificateAction, CertificateInteractionEvent, CertificateTrustUpdateEvent,
+            CertificateVerificationConnectionAbortEvent, CertificateVerificationMode,
+        },
+        connection::{
+            classify_connect_error, ClientSideConnection, ConnectionConfiguration,
+            ConnectionFailedEvent, ConnectionFailureReason, ConnectionId, ConnectionState,
+            InternalAsyncMessage, PendingCertificateInteraction,
+        },
+    },
+    shared::{AsyncRuntime, AsyncRuntimeConfig, QuinnetError, TransportConfig},
+};
+
+pub mod certificate;
+pub mod connection;
+
+pub use certificate::DEFAULT_KNOWN_HOSTS_FILE;
+
+/// Adds client networking to a Bevy app: the [`Client`] resource plus the certificate events.
+#[derive(Default)]
+pub struct QuinnetClientPlugin {
+    pub runtime_config: AsyncRuntimeConfig,
+}
+
+impl Plugin for QuinnetClientPlugin {
+    fn build(&self, app: &mut App) {
+        let runtime = AsyncRuntime::from_config(&self.runtime_config)
+            .expect("failed to build the async runtime backing QuinnetClientPlugin");
+
+        app.insert_resource(runtime.clone())
+            .insert_resource(Client::new(runtime))
+            .add_event::<CertificateTrustUpdateEvent>()
+            .add_event::<CertificateInteractionEvent>()
+            .add_event::<CertificateVerificationConnectionAbortEvent>()
+            .add_event::<ConnectionFailedEvent>()
+            .add_systems(PreUpdate, update_sync_client);
+    }
+}
+
+/// Owns every client-side connection (attempted or established) and the async runtime driving
+/// them.
+#[derive(Resource)]
+pub struct Client {
+    runtime: AsyncRuntime,
+    connections: HashMap<ConnectionId, ClientSideConnection>,
+    default_connection_id: Option<ConnectionId>,
+    last_connection_id: u64,
+    internal_sender: mpsc::Sender<InternalAsyncMessage>,
+    internal_receiver: mpsc::Receiver<InternalAsyncMessage>,
+}
+
+impl Client {
+    pub(crate) fn new(runtime: AsyncRuntime) -> Self {
+        let (internal_sender, internal_receiver) = mpsc::channel();
+        Self {
+            runtime,
+            connections: HashMap::new(),
+            default_connection_id: None,
+            last_connection_id: 0,
+            internal_sender,
+            internal_receiver,
+        }
+    }
+
+    /// Starts a new connection attempt to the server described by `config`, verifying its
+    /// certificate according to `cert_mode`. Returns the id of the connection, which may still
+    /// be in progress by the time this call returns.
+    pub fn open_connection(
+        &mut self,
+        config: ConnectionConfiguration,
+        transport_config: Arc<TransportConfig>,
+        cert_mode: CertificateVerificationMode,
+    ) -> Result<ConnectionId, QuinnetError> {
+        self.last_connection_id += 1;
+        let connection_id = ConnectionId(self.last_connection_id);
+
+        let client_config = certificate::build_client_config(
+            &cert_mode,
+            &config.server_hostname,
+            connection_id,
+            self.internal_sender.clone(),
+            &config.alpn_protocols,
+        )?;
+        let mut quinn_client_config = quinn::ClientConfig::new(Arc::new(client_config));
+        quinn_client_config.transport_config(transport_config);
+
+        let mut endpoint =
+            Endpoint::client(config.local_bind_addr).map_err(QuinnetError::EndpointCreation)?;
+        endpoint.set_default_client_config(quinn_client_config);
+
+        let connecting = endpoint
+            .connect(config.server_addr, &config.server_hostname)
+            .map_err(QuinnetError::ConnectConfigure)?;
+
+        let internal_sender = self.internal_sender.clone();
+        let connect_timeout = config.connect_timeout;
+        let join_handle = self.runtime.spawn(async move {
+            // Keep the endpoint alive for the lifetime of the connection attempt; it is dropped
+            // (and stops accepting new work) once this task ends.
+            let _endpoint = endpoint;
+            let outcome = tokio::time::timeout(connect_timeout, connecting).await;
+            let message = match outcome {
+                Ok(Ok(new_connection)) => {
+                    InternalAsyncMessage::Connected(connection_id, new_connection)
+                }
+                Ok(Err(connect_error)) => InternalAsyncMessage::ConnectionFailed(
+                    connection_id,
+                    classify_connect_error(connect_error),
+                ),
+                Err(_elapsed) => {
+                    InternalAsyncMessage::ConnectionFailed(connection_id, QuinnetError::ConnectTimeout)
+                }
+            };
+            let _ = internal_sender.send(message);
+        });
+
+        self.connections.insert(
+            connection_id,
+            ClientSideConnection {
+                state: ConnectionState::Connecting,
+                pending_cert_interaction: None,
+                abort_handle: Some(join_handle.abort_handle()),
+            },
+        );
+        if self.default_connection_id.is_none() {
+            self.default_connection_id = Some(connection_id);
+        }
+
+        Ok(connection_id)
+    }
+
+    /// Cancels a connection attempt still in progress, aborting its handshake task without
+    /// emitting a [`ConnectionFailedEvent`]. No-op errors if the connection is unknown or has
+    /// already settled (connected or failed).
+    pub fn cancel_connection(&mut self, connection_id: ConnectionId) -> Result<(), QuinnetError> {
+        let connection = self
+            .connections
+            .get_mut(&connection_id)
+            .ok_or(QuinnetError::UnknownConnection(connection_id))?;
+        let abort_handle = connection
+            .abort_handle
+            .take()
+            .ok_or(QuinnetError::ConnectionAlreadyClosed)?;
+        abort_handle.abort();
+        connection.state = ConnectionState::Disconnected;
+        Ok(())
+    }
+
+    /// Applies the app's decision for the latest unresolved certificate interaction on
+    /// `connection_id`.
+    pub fn apply_certificate_action(
+        &mut self,
+        connection_id: ConnectionId,
+        action: CertificateAction,
+    ) -> Result<(), QuinnetError> {
+        let connection = self
+            .connections
+            .get_mut(&connection_id)
+            .ok_or(QuinnetError::UnknownConnection(connection_id))?;
+        let PendingCertificateInteraction {
+            status,
+            info,
+            error,
+        } = connection
+            .pending_cert_interaction
+            .take()
+            .ok_or(QuinnetError::CertificateActionAlreadyApplied)?;
+
+        if let CertificateAction::AbortConnection = action {
+            connection.state = ConnectionState::Disconnected;
+            let _ = self.internal_sender.send(InternalAsyncMessage::CertConnectionAbort(
+                connection_id,
+                status,
+                info,
+                error,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Closes every connection, established or still in progress. The next connection opened
+    /// becomes the new default connection.
+    pub fn close_all_connections(&mut self) -> Result<(), QuinnetError> {
+        for connection in self.connections.values_mut() {
+            connection.state = ConnectionState::Disconnected;
+        }
+        self.default_connection_id = None;
+        Ok(())
+    }
+
+    /// The default connection (the first one opened since connections were last all closed),
+    /// if any.
+    pub fn connection(&self) -> ClientConnectionView<'_> {
+        let connection = self
+            .default_connection_id
+            .and_then(|id| self.connections.get(&id));
+        ClientConnectionView { connection }
+    }
+}
+
+/// A read-only view over a client connection's current state.
+pub struct ClientConnectionView<'a> {
+    connection: Option<&'a ClientSideConnection>,
+}
+
+impl<'a> ClientConnectionView<'a> {
+    pub fn is_connected(&self) -> bool {
+        self.connection.map(ClientSideConnection::is_connected).unwrap_or(false)
+    }
+}
+
+pub(crate) fn update_sync_client(
+    mut client: ResMut<Client>,
+    mut trust_update_events: EventWriter<CertificateTrustUpdateEvent>,
+    mut interaction_events: EventWriter<CertificateInteractionEvent>,
+    mut abort_events: EventWriter<CertificateVerificationConnectionAbortEvent>,
+    mut connection_failed_events: EventWriter<ConnectionFailedEvent>,
+) {
+    while let Ok(message) = client.internal_receiver.try_recv() {
+        match message {
+            InternalAsyncMessage::Connected(connection_id, new_connection) => {
+                if let Some(connection) = client.connections.get_mut(&connection_id) {
+                    // `cancel_connection` aborts the task but cannot guarantee it stops before
+                    // this message was already queued; once cancelled the connection has moved
+                    // out of `Connecting`, so a late `Connected` must not resurrect it.
+                    if matches!(connection.state, ConnectionState::Connecting) {
+                        connection.state = ConnectionState::Connected(new_connection);
+                        connection.abort_handle = None;
+                    }
+                }
+            }
+            InternalAsyncMessage::ConnectionFailed(connection_id, err) => {
+                let was_cancelled = client
+                    .connections
+                    .get(&connection_id)
+                    .map(|connection| !matches!(connection.state, ConnectionState::Connecting))
+                    .unwrap_or(true);
+                if let Some(connection) = client.connections.get_mut(&connection_id) {
+                    if matches!(connection.state, ConnectionState::Connecting) {
+                        connection.state = ConnectionState::Disconnected;
+                    }
+                    connection.abort_handle = None;
+                }
+                // Same race as above: a cancelled attempt can still report a failure after the
+                // fact, which should not surface as a spurious `ConnectionFailedEvent`.
+                if !was_cancelled {
+                    connection_failed_events.send(ConnectionFailedEvent {
+                        connection_id,
+                        reason: ConnectionFailureReason::from(err),
+                    });
+                }
+            }
+            InternalAsyncMessage::CertTrustUpdate(_connection_id, cert_info) => {
+                trust_update_events.send(CertificateTrustUpdateEvent { cert_info });
+            }
+            InternalAsyncMessage::CertInteraction(connection_id, status, info, error) => {
+                if let Some(connection) = client.connections.get_mut(&connection_id) {
+                    connection.pending_cert_interaction = Some(PendingCertificateInteraction {
+                        status,
+                        info: info.clone(),
+                        error: error.clone(),
+                    });
+                }
+                interaction_events.send(CertificateInteractionEvent {
+                    connection_id,
+                    status,
+                    info,
+                    error,
+                });
+            }
+            InternalAsyncMessage::CertConnectionAbort(connection_id, status, info, error) => {
+                abort_events.send(CertificateVerificationConnectionAbortEvent {
+                    connection_id,
+                    status,
+                    info,
+                    error,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::{fs, io::BufReader, path::Path};
+
+use rcgen::{Certificate as RcgenCertificate, CertificateParams};
+use rustls::{Certificate, PrivateKey};
+use rustls_pemfile::Item;
+
+use crate::shared::{CertificateFingerprint, QuinnetError};
+
+/// How the server should obtain the certificate (and associated private key) it presents to
+/// clients during the TLS handshake.
+#[derive(Debug, Clone)]
+pub enum CertificateRetrievalMode {
+    /// Generate a new self-signed certificate every time the endpoint starts.
+    GenerateSelfSigned { server_hostname: String },
+    /// Load a certificate chain and its private key from PEM files on disk.
+    LoadFromFile { cert_file: String, key_file: String },
+}
+
+/// A certificate chain (leaf first, followed by any intermediates) and its matching private key,
+/// ready to be handed to a rustls `ServerConfig`.
+pub(crate) struct ServerCertificate {
+    pub(crate) chain: Vec<Certificate>,
+    pub(crate) key: PrivateKey,
+    /// Fingerprint of the leaf certificate, used by clients doing TOFU pinning.
+    pub(crate) fingerprint: CertificateFingerprint,
+}
+
+pub(crate) fn retrieve_certificate(
+    mode: &CertificateRetrievalMode,
+) -> Result<ServerCertificate, QuinnetError> {
+    match mode {
+        CertificateRetrievalMode::GenerateSelfSigned { server_hostname } => {
+            let cert = RcgenCertificate::from_params(CertificateParams::new(vec![
+                server_hostname.clone()
+            ]))?;
+            let cert_der = cert.serialize_der()?;
+            let key_der = cert.serialize_private_key_der();
+
+            let leaf = Certificate(cert_der);
+            let fingerprint = CertificateFingerprint::from(&leaf);
+            Ok(ServerCertificate {
+                chain: vec![leaf],
+                key: PrivateKey(key_der),
+                fingerprint,
+            })
+        }
+        CertificateRetrievalMode::LoadFromFile {
+            cert_file,
+            key_file,
+        } => load_certificate_chain_from_file(cert_file, key_file),
+    }
+}
+
+/// Reads every certificate in `cert_file` into the chain (leaf + any intermediates) and the
+/// single private key in `key_file`. The fingerprint is computed over the leaf (first)
+/// certificate in the chain.
+fn load_certificate_chain_from_file(
+    cert_file: &str,
+    key_file: &str,
+) -> Result<ServerCertificate, QuinnetError> {
+    let cert_chain_pem = fs::File::open(Path::new(cert_file)).map_err(QuinnetError::IoError)?;
+    let chain: Vec<Certificate> = rustls_pemfile::certs(&mut BufReader::new(cert_chain_pem))
+        .map_err(QuinnetError::IoError)?
+        .into_iter()
+        .map(Certificate)
+        .collect();
+    let leaf = chain.first().ok_or(QuinnetError::NoCertificateFound)?.clone();
+    let fingerprint = CertificateFingerprint::from(&leaf);
+
+    let key = load_private_key_from_file(key_file)?;
+
+    Ok(ServerCertificate {
+        chain,
+        key,
+        fingerprint,
+    })
+}
+
+/// Reads `key_file` block by block, returning the first private key found, whichever of
+/// PKCS#8, PKCS#1 (RSA) or SEC1 (EC) encoding it is in (chain parsing itself is handled by
+/// [`load_certificate_chain_from_file`]; this is only responsible for the key format).
+fn load_private_key_from_file(key_file: &str) -> Result<PrivateKey, QuinnetError> {
+    let key_pem = fs::File::open(Path::new(key_file)).map_err(QuinnetError::IoError)?;
+    let mut reader = BufReader::new(key_pem);
+
+    loop {
+        match rustls_pemfile::read_one(&mut reader).map_err(QuinnetError::IoError)? {
+            Some(Item::PKCS8Key(key) | Item::RSAKey(key) | Item::ECKey(key)) => {
+                return Ok(PrivateKey(key))
+            }
+            Some(_) => continue,
+            None => return Err(QuinnetError::NoPrivateKeyFound),
+        }
+    }
+}
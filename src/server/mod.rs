@@ -0,0 +1,148 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use bevy::prelude::{App, Plugin, Resource};
+use quinn::Endpoint;
+use rustls::ServerConfig as RustlsServerConfig;
+use tokio::task::JoinHandle;
+
+use crate::{
+    server::certificate::CertificateRetrievalMode,
+    shared::{AsyncRuntime, AsyncRuntimeConfig, CertificateFingerprint, QuinnetError},
+};
+
+pub mod certificate;
+
+/// Describes the address a [`Server`] endpoint listens on, and how its TLS handshake behaves.
+#[derive(Debug, Clone)]
+pub struct ServerConfiguration {
+    pub local_bind_addr: SocketAddr,
+    /// ALPN protocol identifiers this endpoint advertises and accepts. Leave empty to advertise
+    /// none: clients advertising no ALPN identifier are then accepted, while a client advertising
+    /// any is refused, as QUIC requires a protocol to be selected once either side offers one.
+    /// Peers presenting none of these are refused at handshake time, rather than after a failed
+    /// deserialization further down the line.
+    pub alpn_protocols: Vec<Vec<u8>>,
+}
+
+impl ServerConfiguration {
+    pub fn from_ip(ip: IpAddr, port: u16) -> Self {
+        Self {
+            local_bind_addr: SocketAddr::new(ip, port),
+            alpn_protocols: Vec::new(),
+        }
+    }
+
+    /// Sets the ALPN protocol identifiers this endpoint advertises and accepts.
+    pub fn with_alpn_protocols(mut self, alpn_protocols: Vec<Vec<u8>>) -> Self {
+        self.alpn_protocols = alpn_protocols;
+        self
+    }
+}
+
+/// Public info about the certificate a [`Server::start_endpoint`] call ended up presenting.
+#[derive(Debug, Clone)]
+pub struct ServerCertificate {
+    pub fingerprint: CertificateFingerprint,
+}
+
+/// Builds the rustls [`RustlsServerConfig`] used by a QUIC endpoint: the certificate/key pair
+/// obtained through `cert_mode`, plus the configured ALPN protocol identifiers.
+pub(crate) fn build_server_rustls_config(
+    cert_mode: &CertificateRetrievalMode,
+    alpn_protocols: &[Vec<u8>],
+) -> Result<(Arc<RustlsServerConfig>, certificate::ServerCertificate), QuinnetError> {
+    let server_cert = certificate::retrieve_certificate(cert_mode)?;
+
+    let mut config = RustlsServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(server_cert.chain.clone(), server_cert.key.clone())
+        .map_err(QuinnetError::RustlsError)?;
+    config.alpn_protocols = alpn_protocols.to_vec();
+
+    Ok((Arc::new(config), server_cert))
+}
+
+/// Adds server networking to a Bevy app: the [`Server`] resource.
+#[derive(Default)]
+pub struct QuinnetServerPlugin {
+    pub runtime_config: AsyncRuntimeConfig,
+}
+
+impl Plugin for QuinnetServerPlugin {
+    fn build(&self, app: &mut App) {
+        let runtime = AsyncRuntime::from_config(&self.runtime_config)
+            .expect("failed to build the async runtime backing QuinnetServerPlugin");
+
+        app.insert_resource(Server::new(runtime));
+    }
+}
+
+/// The running endpoint of a [`Server`], if one has been started.
+struct RunningEndpoint {
+    accept_loop: JoinHandle<()>,
+}
+
+/// Owns the server's QUIC endpoint and the async runtime driving it.
+#[derive(Resource)]
+pub struct Server {
+    runtime: AsyncRuntime,
+    endpoint: Option<RunningEndpoint>,
+}
+
+impl Server {
+    pub(crate) fn new(runtime: AsyncRuntime) -> Self {
+        Self {
+            runtime,
+            endpoint: None,
+        }
+    }
+
+    /// Starts listening for incoming connections on `config.local_bind_addr`, presenting the
+    /// certificate obtained through `cert_mode` and requiring a matching ALPN protocol if
+    /// `config.alpn_protocols` is non-empty.
+    pub fn start_endpoint(
+        &mut self,
+        config: ServerConfiguration,
+        cert_mode: CertificateRetrievalMode,
+    ) -> Result<(ServerCertificate, ()), QuinnetError> {
+        let (rustls_config, server_cert) =
+            build_server_rustls_config(&cert_mode, &config.alpn_protocols)?;
+        let quinn_server_config = quinn::ServerConfig::with_crypto(rustls_config);
+
+        let endpoint = Endpoint::server(quinn_server_config, config.local_bind_addr)
+            .map_err(QuinnetError::EndpointCreation)?;
+
+        let accept_loop = self.runtime.spawn(async move {
+            while let Some(connecting) = endpoint.accept().await {
+                // Only the handshake is driven here, so TLS/ALPN mismatches are rejected instead
+                // of left to time out. The established connection is then dropped: no client is
+                // registered and no stream is routed.
+                let _ = connecting.await;
+            }
+        });
+
+        self.endpoint = Some(RunningEndpoint { accept_loop });
+
+        Ok((
+            ServerCertificate {
+                fingerprint: server_cert.fingerprint,
+            },
+            (),
+        ))
+    }
+
+    /// Stops the currently running endpoint, if any.
+    pub fn stop_endpoint(&mut self) -> Result<(), QuinnetError> {
+        match self.endpoint.take() {
+            Some(endpoint) => {
+                endpoint.accept_loop.abort();
+                Ok(())
+            }
+            None => Err(QuinnetError::EndpointAlreadyClosed),
+        }
+    }
+}
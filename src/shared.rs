@@ -1,4 +1,4 @@
-use std::{fmt, io, net::AddrParseError, sync::PoisonError};
+use std::{fmt, io, net::AddrParseError, sync::Arc, sync::PoisonError};
 
 use crate::client::connection::ConnectionId;
 use bevy::prelude::{Deref, DerefMut, Resource};
@@ -40,8 +40,72 @@ impl std::fmt::Display for ClientId {
 
 pub mod channel;
 
-#[derive(Resource, Deref, DerefMut)]
-pub struct AsyncRuntime(pub(crate) Runtime);
+/// Default name given to the worker threads of a runtime built from
+/// [`AsyncRuntimeSource::New`].
+pub const DEFAULT_RUNTIME_THREAD_NAME: &str = "bevy_quinnet-async";
+
+/// Where [`QuinnetClientPlugin`](crate::client::QuinnetClientPlugin) and
+/// [`QuinnetServerPlugin`](crate::server::QuinnetServerPlugin) get the tokio runtime they run
+/// their async networking tasks on.
+#[derive(Clone)]
+pub enum AsyncRuntimeSource {
+    /// Build a dedicated multi-threaded runtime with the given settings.
+    New {
+        /// Number of worker threads. Defaults to the number of logical CPUs if `None`.
+        worker_threads: Option<usize>,
+        /// Name given to every one of the runtime's worker threads.
+        thread_name: String,
+    },
+    /// Reuse a runtime the app already owns, instead of spinning up a second one.
+    Shared(Arc<Runtime>),
+}
+
+impl Default for AsyncRuntimeSource {
+    fn default() -> Self {
+        Self::New {
+            worker_threads: None,
+            thread_name: DEFAULT_RUNTIME_THREAD_NAME.to_string(),
+        }
+    }
+}
+
+/// Config for the tokio runtime backing a [`QuinnetClientPlugin`](crate::client::QuinnetClientPlugin)
+/// or [`QuinnetServerPlugin`](crate::server::QuinnetServerPlugin).
+#[derive(Clone, Default)]
+pub struct AsyncRuntimeConfig {
+    pub source: AsyncRuntimeSource,
+}
+
+#[derive(Resource, Clone)]
+pub struct AsyncRuntime(pub(crate) Arc<Runtime>);
+
+impl AsyncRuntime {
+    pub(crate) fn from_config(config: &AsyncRuntimeConfig) -> io::Result<Self> {
+        match &config.source {
+            AsyncRuntimeSource::New {
+                worker_threads,
+                thread_name,
+            } => {
+                let mut builder = tokio::runtime::Builder::new_multi_thread();
+                builder.enable_all().thread_name(thread_name.clone());
+                if let Some(worker_threads) = worker_threads {
+                    builder.worker_threads(*worker_threads);
+                }
+                Ok(AsyncRuntime(Arc::new(builder.build()?)))
+            }
+            AsyncRuntimeSource::Shared(runtime) => Ok(AsyncRuntime(runtime.clone())),
+        }
+    }
+}
+
+impl std::ops::Deref for AsyncRuntime {
+    type Target = Runtime;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 pub(crate) type InternalConnectionRef = quinn::Connection;
 
 /// Enum with possibles errors that can occur in Bevy Quinnet
@@ -77,8 +141,6 @@ pub enum QuinnetError {
     FullQueue,
     #[error("receiving half of the internal channel was explicitly closed or has been dropped")]
     InternalChannelClosed,
-    #[error("hosts file is invalid")]
-    InvalidHostFile,
     #[error("lock acquisition failure")]
     LockAcquisitionFailure,
     #[error("certificate action was already sent for a CertificateInteractionEvent")]
@@ -109,6 +171,14 @@ pub enum QuinnetError {
     SignalConnectionToClient,
     #[error("failed to signal connection lost to sync client")]
     SignalConnectionLostToClient,
+    #[error("connection attempt to the server timed out")]
+    ConnectTimeout,
+    #[error("peer does not support any of the requested ALPN protocols")]
+    AlpnNegotiationFailed,
+    #[error("no supported private key (PKCS#8, PKCS#1 or SEC1) found in key file")]
+    NoPrivateKeyFound,
+    #[error("no certificate found in certificate file")]
+    NoCertificateFound,
 }
 
 impl<T> From<PoisonError<T>> for QuinnetError {
@@ -7,7 +7,7 @@ use bevy::{
 use bevy_quinnet::{
     client::{
         self,
-        certificate::{CertVerificationStatus, CertificateVerificationMode},
+        certificate::{CertVerificationError, CertVerificationStatus, CertificateVerificationMode},
         Client, QuinnetClientPlugin, DEFAULT_KNOWN_HOSTS_FILE,
     },
     server::{
@@ -15,6 +15,7 @@ use bevy_quinnet::{
     },
     shared::TransportConfig,
 };
+use rcgen::{date_time_ymd, BasicConstraints, Certificate as RcgenCertificate, CertificateParams, IsCa};
 
 // https://github.com/rust-lang/rust/issues/46379
 pub use utils::*;
@@ -314,3 +315,252 @@ fn trust_on_first_use() {
     // Leave the workspace clean
     fs::remove_file(DEFAULT_KNOWN_HOSTS_FILE).expect("failed to remove default known hosts file");
 }
+
+#[test]
+fn trust_standard_roots() {
+    // CertificateVerificationMode::TrustStandardRoots
+    // Server listens with a leaf certificate signed by a local CA
+    // Client connects trusting only that CA (via `additional_root_certs_pem_files`)
+    // -> The connection succeeds without any TOFU-style certificate interaction
+
+    let port = 6005; // TODO Use port 0 and retrieve the port used by the server.
+
+    let ca_cert_file = "assets/tests/trust_standard_roots_ca.pem.test";
+    let leaf_cert_file = "assets/tests/trust_standard_roots_leaf.pem.test";
+    let leaf_key_file = "assets/tests/trust_standard_roots_leaf_key.pem.test";
+
+    // Generate a local CA and a leaf certificate it signs for our server name, so the test does
+    // not depend on a real, internet-trusted CA.
+    let mut ca_params = CertificateParams::new(Vec::new());
+    ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    let ca_cert = RcgenCertificate::from_params(ca_params).expect("failed to generate test CA");
+
+    let leaf_params = CertificateParams::new(vec![SERVER_IP.to_string()]);
+    let leaf_cert = RcgenCertificate::from_params(leaf_params).expect("failed to generate leaf cert");
+    let leaf_cert_pem = leaf_cert
+        .serialize_pem_with_signer(&ca_cert)
+        .expect("failed to sign leaf cert with test CA");
+
+    fs::create_dir_all("assets/tests").expect("failed to create assets/tests");
+    fs::write(ca_cert_file, ca_cert.serialize_pem().unwrap()).unwrap();
+    fs::write(leaf_cert_file, leaf_cert_pem).unwrap();
+    fs::write(leaf_key_file, leaf_cert.serialize_private_key_pem()).unwrap();
+
+    let mut client_app = App::new();
+    client_app
+        .add_plugins((
+            ScheduleRunnerPlugin::default(),
+            QuinnetClientPlugin::default(),
+        ))
+        .insert_resource(ClientTestData::default())
+        .add_systems(Update, handle_client_events);
+
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((
+            ScheduleRunnerPlugin::default(),
+            QuinnetServerPlugin::default(),
+        ))
+        .insert_resource(ServerTestData::default())
+        .add_systems(Update, handle_server_events);
+
+    // Startup
+    client_app.update();
+    server_app.update();
+
+    // Server listens with the CA-signed leaf certificate
+    {
+        let mut server = server_app.world.resource_mut::<Server>();
+        server
+            .start_endpoint(
+                ServerConfiguration::from_ip("0.0.0.0".parse().unwrap(), port),
+                CertificateRetrievalMode::LoadFromFile {
+                    cert_file: leaf_cert_file.to_string(),
+                    key_file: leaf_key_file.to_string(),
+                },
+            )
+            .unwrap();
+    }
+
+    // Client connects trusting only our local CA
+    {
+        let mut client = client_app.world.resource_mut::<Client>();
+        client
+            .open_connection(
+                default_client_configuration(port),
+                Arc::new(TransportConfig::default()),
+                CertificateVerificationMode::TrustStandardRoots(
+                    client::certificate::TrustStandardRootsConfig {
+                        additional_root_certs_pem_files: vec![ca_cert_file.to_string()],
+                    },
+                ),
+            )
+            .unwrap();
+    }
+
+    // Let the async runtime connect.
+    sleep(Duration::from_secs_f32(0.1));
+
+    server_app.update();
+    client_app.update();
+
+    assert!(
+        client_app
+            .world
+            .resource_mut::<Client>()
+            .connection()
+            .is_connected(),
+        "The client should trust the CA-signed certificate and connect without any TOFU interaction"
+    );
+    let client_test_data = client_app.world.resource::<ClientTestData>();
+    assert_eq!(
+        client_test_data.cert_interactions_received, 0,
+        "TrustStandardRoots should not raise a TOFU-style certificate interaction for a CA-signed cert"
+    );
+
+    // Leave the workspace clean
+    fs::remove_file(ca_cert_file).unwrap();
+    fs::remove_file(leaf_cert_file).unwrap();
+    fs::remove_file(leaf_key_file).unwrap();
+}
+
+#[test]
+fn trust_on_first_use_reports_concrete_certificate_fault() {
+    // TOFU with a server presenting an already-expired certificate after the client has pinned
+    // a different (valid) one for that server name
+    // -> The resulting certificate interaction event should carry
+    //    `CertVerificationError::Expired`, not just a bare fingerprint mismatch
+
+    let port = 6006; // TODO Use port 0 and retrieve the port used by the server.
+    let known_hosts_file = "assets/tests/trust_on_first_use_fault_known_hosts.test";
+    let expired_cert_file = "assets/tests/trust_on_first_use_fault_expired.pem.test";
+    let expired_key_file = "assets/tests/trust_on_first_use_fault_expired_key.pem.test";
+
+    if Path::new(known_hosts_file).exists() {
+        fs::remove_file(known_hosts_file).expect("failed to remove known hosts file");
+    }
+
+    let mut client_app = App::new();
+    client_app
+        .add_plugins((
+            ScheduleRunnerPlugin::default(),
+            QuinnetClientPlugin::default(),
+        ))
+        .insert_resource(ClientTestData::default())
+        .add_systems(Update, handle_client_events);
+
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((
+            ScheduleRunnerPlugin::default(),
+            QuinnetServerPlugin::default(),
+        ))
+        .insert_resource(ServerTestData::default())
+        .add_systems(Update, handle_server_events);
+
+    client_app.update();
+    server_app.update();
+
+    let tofu_config = || client::certificate::TrustOnFirstUseConfig {
+        known_hosts_file: known_hosts_file.to_string(),
+    };
+
+    // Server listens with the known test cert, client pins it
+    {
+        let mut server = server_app.world.resource_mut::<Server>();
+        server
+            .start_endpoint(
+                ServerConfiguration::from_ip("0.0.0.0".parse().unwrap(), port),
+                CertificateRetrievalMode::LoadFromFile {
+                    cert_file: TEST_CERT_FILE.to_string(),
+                    key_file: TEST_KEY_FILE.to_string(),
+                },
+            )
+            .unwrap();
+    }
+    {
+        let mut client = client_app.world.resource_mut::<Client>();
+        client
+            .open_connection(
+                default_client_configuration(port),
+                Arc::new(TransportConfig::default()),
+                CertificateVerificationMode::TrustOnFirstUse(tofu_config()),
+            )
+            .unwrap();
+    }
+    sleep(Duration::from_secs_f32(0.1));
+    server_app.update();
+    client_app.update();
+    client_app
+        .world
+        .resource_mut::<Client>()
+        .close_all_connections()
+        .expect("failed to close connections on the client");
+
+    // Server reboots presenting a certificate that is both a different fingerprint AND already
+    // expired
+    server_app
+        .world
+        .resource_mut::<Server>()
+        .stop_endpoint()
+        .unwrap();
+    sleep(Duration::from_secs_f32(0.1));
+
+    let mut expired_params = CertificateParams::new(vec![SERVER_IP.to_string()]);
+    expired_params.not_before = date_time_ymd(2000, 1, 1);
+    expired_params.not_after = date_time_ymd(2000, 1, 2);
+    let expired_cert =
+        RcgenCertificate::from_params(expired_params).expect("failed to generate expired cert");
+    fs::create_dir_all("assets/tests").expect("failed to create assets/tests");
+    fs::write(expired_cert_file, expired_cert.serialize_pem().unwrap()).unwrap();
+    fs::write(expired_key_file, expired_cert.serialize_private_key_pem()).unwrap();
+
+    server_app
+        .world
+        .resource_mut::<Server>()
+        .start_endpoint(
+            ServerConfiguration::from_ip(LOCAL_BIND_IP, port),
+            CertificateRetrievalMode::LoadFromFile {
+                cert_file: expired_cert_file.to_string(),
+                key_file: expired_key_file.to_string(),
+            },
+        )
+        .unwrap();
+
+    {
+        let mut client = client_app.world.resource_mut::<Client>();
+        client
+            .open_connection(
+                default_client_configuration(port),
+                Arc::new(TransportConfig::default()),
+                CertificateVerificationMode::TrustOnFirstUse(tofu_config()),
+            )
+            .unwrap();
+    }
+    sleep(Duration::from_secs_f32(0.1));
+    server_app.update();
+    client_app.update();
+
+    {
+        let client_test_data = client_app.world.resource::<ClientTestData>();
+        assert_eq!(
+            client_test_data.cert_interactions_received, 1,
+            "The client should have received exactly 1 certificate interaction event"
+        );
+        assert_eq!(
+            client_test_data.last_cert_interactions_status,
+            Some(CertVerificationStatus::UntrustedCertificate),
+            "The mismatched certificate should be reported as untrusted"
+        );
+        assert_eq!(
+            client_test_data.last_cert_interactions_error,
+            Some(CertVerificationError::Expired),
+            "The certificate interaction should carry the concrete `Expired` fault, not just a bare fingerprint mismatch"
+        );
+    }
+
+    // Leave the workspace clean
+    fs::remove_file(known_hosts_file).expect("failed to remove known hosts file");
+    fs::remove_file(expired_cert_file).unwrap();
+    fs::remove_file(expired_key_file).unwrap();
+}
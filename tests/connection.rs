@@ -0,0 +1,217 @@
+use std::{net::Ipv4Addr, sync::Arc, thread::sleep, time::Duration};
+
+use bevy::{
+    app::ScheduleRunnerPlugin,
+    prelude::{App, Update},
+};
+use bevy_quinnet::{
+    client::{
+        certificate::CertificateVerificationMode, connection::ConnectionFailureReason, Client,
+        QuinnetClientPlugin,
+    },
+    server::{certificate::CertificateRetrievalMode, QuinnetServerPlugin, Server, ServerConfiguration},
+    shared::TransportConfig,
+};
+
+// https://github.com/rust-lang/rust/issues/46379
+pub use utils::*;
+
+mod utils;
+
+///////////////////////////////////////////////////////////
+///                                                     ///
+///                        Test                         ///
+///                                                     ///
+///////////////////////////////////////////////////////////
+
+#[test]
+fn connect_timeout() {
+    // Connecting to an address nothing answers on should fail with `ConnectionFailureReason::ConnectTimeout`
+    // rather than hanging forever or reporting a generic connect failure.
+
+    let mut client_app = App::new();
+    client_app
+        .add_plugins((
+            ScheduleRunnerPlugin::default(),
+            QuinnetClientPlugin::default(),
+        ))
+        .insert_resource(ClientTestData::default())
+        .add_systems(Update, handle_client_events);
+
+    client_app.update();
+
+    // TEST-NET-1 (RFC 5737): reserved for documentation, nothing will ever answer on it, so the
+    // handshake simply never completes instead of being refused outright.
+    let unreachable_addr = Ipv4Addr::new(192, 0, 2, 1);
+
+    {
+        let mut client = client_app.world.resource_mut::<Client>();
+        client
+            .open_connection(
+                bevy_quinnet::client::connection::ConnectionConfiguration::from_ips(
+                    unreachable_addr.into(),
+                    6007,
+                    LOCAL_BIND_IP,
+                    0,
+                )
+                .with_connect_timeout(Duration::from_millis(200)),
+                Arc::new(TransportConfig::default()),
+                CertificateVerificationMode::SkipVerification,
+            )
+            .unwrap();
+    }
+
+    // Let the timeout elapse.
+    sleep(Duration::from_millis(400));
+    client_app.update();
+
+    let client_test_data = client_app.world.resource::<ClientTestData>();
+    assert_eq!(
+        client_test_data.connection_failed_events_received, 1,
+        "The client should have received exactly 1 connection-failed event"
+    );
+    assert_eq!(
+        client_test_data.last_connection_failed_reason,
+        Some(ConnectionFailureReason::ConnectTimeout),
+        "The connection attempt should fail with `ConnectTimeout`"
+    );
+    assert!(
+        !client_app
+            .world
+            .resource::<Client>()
+            .connection()
+            .is_connected(),
+        "The connection should not be established"
+    );
+}
+
+#[test]
+fn alpn_mismatch_fails_with_specific_error() {
+    // A server and client configured with disjoint ALPN protocol lists should fail the handshake
+    // with `ConnectionFailureReason::AlpnNegotiationFailed`, not a generic connect failure.
+
+    let port = 6009; // TODO Use port 0 and retrieve the port used by the server.
+
+    let mut client_app = App::new();
+    client_app
+        .add_plugins((
+            ScheduleRunnerPlugin::default(),
+            QuinnetClientPlugin::default(),
+        ))
+        .insert_resource(ClientTestData::default())
+        .add_systems(Update, handle_client_events);
+
+    let mut server_app = App::new();
+    server_app
+        .add_plugins((
+            ScheduleRunnerPlugin::default(),
+            QuinnetServerPlugin::default(),
+        ))
+        .insert_resource(ServerTestData::default())
+        .add_systems(Update, handle_server_events);
+
+    client_app.update();
+    server_app.update();
+
+    {
+        let mut server = server_app.world.resource_mut::<Server>();
+        server
+            .start_endpoint(
+                ServerConfiguration::from_ip("0.0.0.0".parse().unwrap(), port)
+                    .with_alpn_protocols(vec![b"server-proto".to_vec()]),
+                CertificateRetrievalMode::GenerateSelfSigned {
+                    server_hostname: SERVER_IP.to_string(),
+                },
+            )
+            .unwrap();
+    }
+
+    {
+        let mut client = client_app.world.resource_mut::<Client>();
+        client
+            .open_connection(
+                default_client_configuration(port).with_alpn_protocols(vec![b"client-proto".to_vec()]),
+                Arc::new(TransportConfig::default()),
+                CertificateVerificationMode::SkipVerification,
+            )
+            .unwrap();
+    }
+
+    sleep(Duration::from_millis(200));
+    server_app.update();
+    client_app.update();
+
+    let client_test_data = client_app.world.resource::<ClientTestData>();
+    assert_eq!(
+        client_test_data.connection_failed_events_received, 1,
+        "The client should have received exactly 1 connection-failed event"
+    );
+    assert_eq!(
+        client_test_data.last_connection_failed_reason,
+        Some(ConnectionFailureReason::AlpnNegotiationFailed),
+        "Disjoint ALPN protocol lists should fail the handshake with `AlpnNegotiationFailed`"
+    );
+}
+
+#[test]
+fn cancel_connection_aborts_pending_attempt() {
+    // Cancelling a connection attempt still in progress should abort its handshake task without
+    // ever reporting it as connected or failed.
+
+    let mut client_app = App::new();
+    client_app
+        .add_plugins((
+            ScheduleRunnerPlugin::default(),
+            QuinnetClientPlugin::default(),
+        ))
+        .insert_resource(ClientTestData::default())
+        .add_systems(Update, handle_client_events);
+
+    client_app.update();
+
+    let unreachable_addr = Ipv4Addr::new(192, 0, 2, 1);
+
+    let connection_id = {
+        let mut client = client_app.world.resource_mut::<Client>();
+        client
+            .open_connection(
+                bevy_quinnet::client::connection::ConnectionConfiguration::from_ips(
+                    unreachable_addr.into(),
+                    6008,
+                    LOCAL_BIND_IP,
+                    0,
+                )
+                .with_connect_timeout(Duration::from_secs(30)),
+                Arc::new(TransportConfig::default()),
+                CertificateVerificationMode::SkipVerification,
+            )
+            .unwrap()
+    };
+
+    {
+        let mut client = client_app.world.resource_mut::<Client>();
+        client
+            .cancel_connection(connection_id)
+            .expect("cancelling a pending connection attempt should succeed");
+        // Cancelling twice should report the attempt as already settled.
+        assert!(client.cancel_connection(connection_id).is_err());
+    }
+
+    // Give the aborted task a chance to run (it shouldn't report anything).
+    sleep(Duration::from_millis(100));
+    client_app.update();
+
+    let client_test_data = client_app.world.resource::<ClientTestData>();
+    assert_eq!(
+        client_test_data.connection_failed_events_received, 0,
+        "A cancelled connection attempt should not emit a connection-failed event"
+    );
+    assert!(
+        !client_app
+            .world
+            .resource::<Client>()
+            .connection()
+            .is_connected(),
+        "The cancelled connection should not be connected"
+    );
+}
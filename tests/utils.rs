@@ -0,0 +1,81 @@
+use std::net::{IpAddr, Ipv4Addr};
+
+use bevy::prelude::{EventReader, Res, ResMut, Resource};
+use bevy_quinnet::client::{
+    certificate::{
+        CertVerificationError, CertificateInfo, CertificateInteractionEvent,
+        CertificateTrustUpdateEvent, CertificateVerificationConnectionAbortEvent,
+        CertificateAction, CertVerificationStatus,
+    },
+    connection::{ConnectionConfiguration, ConnectionFailedEvent, ConnectionFailureReason},
+    Client,
+};
+
+pub const SERVER_IP: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+pub const LOCAL_BIND_IP: IpAddr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+
+/// Connection config used by most tests: connect to `SERVER_IP:port`, binding locally to an
+/// OS-assigned port.
+pub fn default_client_configuration(port: u16) -> ConnectionConfiguration {
+    ConnectionConfiguration::from_ips(SERVER_IP, port, LOCAL_BIND_IP, 0)
+}
+
+#[derive(Resource, Default)]
+pub struct ClientTestData {
+    pub cert_trust_update_events_received: i32,
+    pub last_trusted_cert_info: Option<CertificateInfo>,
+
+    pub cert_interactions_received: i32,
+    pub last_cert_interactions_info: Option<CertificateInfo>,
+    pub last_cert_interactions_status: Option<CertVerificationStatus>,
+    pub last_cert_interactions_error: Option<CertVerificationError>,
+
+    pub cert_verif_connection_abort_events_received: i32,
+    pub last_abort_cert_info: Option<CertificateInfo>,
+    pub last_abort_cert_status: Option<CertVerificationStatus>,
+
+    pub connection_failed_events_received: i32,
+    pub last_connection_failed_reason: Option<ConnectionFailureReason>,
+}
+
+#[derive(Resource, Default)]
+pub struct ServerTestData {}
+
+/// Drains client certificate/connection events into [`ClientTestData`], and always responds to
+/// a certificate interaction by asking to abort the connection, mirroring a game prompting the
+/// player and the player declining to trust an unexpected certificate.
+pub fn handle_client_events(
+    mut client: ResMut<Client>,
+    mut test_data: ResMut<ClientTestData>,
+    mut trust_update_events: EventReader<CertificateTrustUpdateEvent>,
+    mut interaction_events: EventReader<CertificateInteractionEvent>,
+    mut abort_events: EventReader<CertificateVerificationConnectionAbortEvent>,
+    mut connection_failed_events: EventReader<ConnectionFailedEvent>,
+) {
+    for event in trust_update_events.read() {
+        test_data.cert_trust_update_events_received += 1;
+        test_data.last_trusted_cert_info = Some(event.cert_info.clone());
+    }
+
+    for event in interaction_events.read() {
+        test_data.cert_interactions_received += 1;
+        test_data.last_cert_interactions_info = Some(event.info.clone());
+        test_data.last_cert_interactions_status = Some(event.status);
+        test_data.last_cert_interactions_error = event.error.clone();
+
+        let _ = client.apply_certificate_action(event.connection_id, CertificateAction::AbortConnection);
+    }
+
+    for event in abort_events.read() {
+        test_data.cert_verif_connection_abort_events_received += 1;
+        test_data.last_abort_cert_info = Some(event.info.clone());
+        test_data.last_abort_cert_status = Some(event.status);
+    }
+
+    for event in connection_failed_events.read() {
+        test_data.connection_failed_events_received += 1;
+        test_data.last_connection_failed_reason = Some(event.reason.clone());
+    }
+}
+
+pub fn handle_server_events(_server_test_data: Res<ServerTestData>) {}